@@ -0,0 +1,340 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+/// Every key-triggered behavior in normal mode, so the event loop can look
+/// one up from the keymap instead of matching on literal `KeyCode`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Up,
+    Down,
+    HalfPageUp,
+    HalfPageDown,
+    ScrollPreviewUp,
+    ScrollPreviewDown,
+    First,
+    Last,
+    Leave,
+    Enter,
+    Find,
+    ToggleHidden,
+    Mounts,
+    CycleSort,
+    ToggleReverse,
+    ToggleDirsFirst,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    Mark,
+    Yank,
+    Cut,
+    Paste,
+    Delete,
+    Select,
+    Quit,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Action, String> {
+        match s {
+            "Up" => Ok(Action::Up),
+            "Down" => Ok(Action::Down),
+            "HalfPageUp" => Ok(Action::HalfPageUp),
+            "HalfPageDown" => Ok(Action::HalfPageDown),
+            "ScrollPreviewUp" => Ok(Action::ScrollPreviewUp),
+            "ScrollPreviewDown" => Ok(Action::ScrollPreviewDown),
+            "First" => Ok(Action::First),
+            "Last" => Ok(Action::Last),
+            "Leave" => Ok(Action::Leave),
+            "Enter" => Ok(Action::Enter),
+            "Find" => Ok(Action::Find),
+            "ToggleHidden" => Ok(Action::ToggleHidden),
+            "Mounts" => Ok(Action::Mounts),
+            "CycleSort" => Ok(Action::CycleSort),
+            "ToggleReverse" => Ok(Action::ToggleReverse),
+            "ToggleDirsFirst" => Ok(Action::ToggleDirsFirst),
+            "NewTab" => Ok(Action::NewTab),
+            "CloseTab" => Ok(Action::CloseTab),
+            "NextTab" => Ok(Action::NextTab),
+            "PrevTab" => Ok(Action::PrevTab),
+            "Mark" => Ok(Action::Mark),
+            "Yank" => Ok(Action::Yank),
+            "Cut" => Ok(Action::Cut),
+            "Paste" => Ok(Action::Paste),
+            "Delete" => Ok(Action::Delete),
+            "Select" => Ok(Action::Select),
+            "Quit" => Ok(Action::Quit),
+            other => Err(format!("unknown action \"{other}\"")),
+        }
+    }
+}
+
+/// A parsed key chord to `Action` dispatch table, built from the built-in
+/// defaults and then overlaid with the user's `[keymap]` table.
+pub struct Keymap(HashMap<(KeyCode, KeyModifiers), Action>);
+
+impl Keymap {
+    pub fn get(&self, key: KeyEvent) -> Option<Action> {
+        self.0.get(&(key.code, key.modifiers)).copied().or_else(|| {
+            // crossterm also sets SHIFT on uppercase letters and BackTab,
+            // which chords spell without a `shift+` prefix.
+            if !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+                return None;
+            }
+            let mut modifiers = key.modifiers;
+            modifiers.remove(KeyModifiers::SHIFT);
+            self.0.get(&(key.code, modifiers)).copied()
+        })
+    }
+
+    fn insert_chord(&mut self, chord: &str, action: Action) {
+        if let Some(key) = parse_chord(chord) {
+            self.0.insert(key, action);
+        }
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        let mut keymap = Keymap(HashMap::new());
+        for (chord, action) in DEFAULT_BINDINGS {
+            keymap.insert_chord(chord, *action);
+        }
+        keymap
+    }
+}
+
+const DEFAULT_BINDINGS: &[(&str, Action)] = &[
+    ("down", Action::Down),
+    ("j", Action::Down),
+    ("J", Action::HalfPageDown),
+    ("up", Action::Up),
+    ("k", Action::Up),
+    ("K", Action::HalfPageUp),
+    ("pagedown", Action::ScrollPreviewDown),
+    ("pageup", Action::ScrollPreviewUp),
+    ("g", Action::First),
+    ("G", Action::Last),
+    ("left", Action::Leave),
+    ("h", Action::Leave),
+    ("right", Action::Enter),
+    ("l", Action::Enter),
+    ("f", Action::Find),
+    ("a", Action::ToggleHidden),
+    ("m", Action::Mounts),
+    ("s", Action::CycleSort),
+    ("r", Action::ToggleReverse),
+    ("d", Action::ToggleDirsFirst),
+    ("t", Action::NewTab),
+    ("w", Action::CloseTab),
+    ("tab", Action::NextTab),
+    ("backtab", Action::PrevTab),
+    ("space", Action::Mark),
+    ("y", Action::Yank),
+    ("x", Action::Cut),
+    ("p", Action::Paste),
+    ("D", Action::Delete),
+    ("q", Action::Select),
+    ("enter", Action::Select),
+    ("esc", Action::Quit),
+];
+
+/// Parses a chord like `"j"`, `"ctrl+d"` or `"shift+tab"`. Letter case is
+/// significant for plain characters (`"J"` is Shift+j already, with no
+/// `shift+` prefix needed): crossterm reports Shift+j as `J`, and
+/// `Keymap::get` ignores the SHIFT flag it sets alongside.
+fn parse_chord(chord: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut parts: Vec<&str> = chord.split('+').collect();
+    let key_part = parts.pop()?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    for part in parts {
+        modifiers |= match part.to_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "shift" => KeyModifiers::SHIFT,
+            "alt" => KeyModifiers::ALT,
+            _ => return None,
+        };
+    }
+
+    let code = match key_part.to_lowercase().as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        _ if key_part.chars().count() == 1 => KeyCode::Char(key_part.chars().next()?),
+        _ => return None,
+    };
+    Some((code, modifiers))
+}
+
+/// Accent color, selection highlight, and icon glyphs — everything that
+/// used to be a CLI flag or a literal in `into_list_item`.
+pub struct Theme {
+    pub accent: Color,
+    pub highlight: Style,
+    pub dir_icon: String,
+    pub file_icon: String,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            accent: Color::Red,
+            highlight: Style::default().add_modifier(Modifier::REVERSED),
+            dir_icon: "\u{f07c}".to_owned(),
+            file_icon: "\u{f15c}".to_owned(),
+        }
+    }
+}
+
+/// The keymap, theme, and scroll step, as built by `Config::load`.
+pub struct Config {
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// How many rows `HalfPageUp`/`HalfPageDown` move by (`J`/`K`, by default).
+    pub half_page_step: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            half_page_step: 5,
+        }
+    }
+}
+
+impl Config {
+    /// Loads `$XDG_CONFIG_HOME/seldir2/config.toml` (falling back to
+    /// `~/.config/seldir2/config.toml`), overlaying it on the defaults.
+    /// A missing or unparsable file silently falls back to the defaults —
+    /// seldir2 should still start without one.
+    pub fn load() -> Config {
+        let mut config = Config::default();
+        let Some(path) = config_path() else {
+            return config;
+        };
+        let Ok(contents) = fs::read_to_string(path) else {
+            return config;
+        };
+        let Ok(raw) = toml::from_str::<RawConfig>(&contents) else {
+            return config;
+        };
+
+        if let Some(accent) = raw.theme.accent.as_deref().and_then(|c| Color::from_str(c).ok()) {
+            config.theme.accent = accent;
+        }
+        if let Some(highlight) = raw.theme.highlight.as_deref() {
+            config.theme.highlight = parse_highlight(highlight);
+        }
+        if let Some(dir_icon) = raw.theme.dir_icon {
+            config.theme.dir_icon = dir_icon;
+        }
+        if let Some(file_icon) = raw.theme.file_icon {
+            config.theme.file_icon = file_icon;
+        }
+        if let Some(step) = raw.general.half_page_step {
+            config.half_page_step = step;
+        }
+        for (chord, action) in raw.keymap {
+            if let Ok(action) = Action::from_str(&action) {
+                config.keymap.insert_chord(&chord, action);
+            }
+        }
+
+        config
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let config_dir = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok()?;
+    Some(config_dir.join("seldir2").join("config.toml"))
+}
+
+fn parse_highlight(s: &str) -> Style {
+    s.split('+').fold(Style::default(), |style, part| {
+        match part.trim().to_lowercase().as_str() {
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "underlined" => style.add_modifier(Modifier::UNDERLINED),
+            "dim" => style.add_modifier(Modifier::DIM),
+            _ => style,
+        }
+    })
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawConfig {
+    #[serde(default)]
+    general: RawGeneral,
+    #[serde(default)]
+    theme: RawTheme,
+    #[serde(default)]
+    keymap: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawGeneral {
+    half_page_step: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct RawTheme {
+    accent: Option<String>,
+    highlight: Option<String>,
+    dir_icon: Option<String>,
+    file_icon: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_chord_plain_char_has_no_modifiers() {
+        assert_eq!(parse_chord("j"), Some((KeyCode::Char('j'), KeyModifiers::NONE)));
+        assert_eq!(parse_chord("J"), Some((KeyCode::Char('J'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn parse_chord_reads_named_keys_and_modifiers() {
+        assert_eq!(parse_chord("tab"), Some((KeyCode::Tab, KeyModifiers::NONE)));
+        assert_eq!(
+            parse_chord("ctrl+d"),
+            Some((KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_chord("shift+tab"),
+            Some((KeyCode::Tab, KeyModifiers::SHIFT))
+        );
+    }
+
+    #[test]
+    fn parse_chord_rejects_unknown_modifier_or_key() {
+        assert_eq!(parse_chord("meta+j"), None);
+        assert_eq!(parse_chord("nonsense"), None);
+    }
+}
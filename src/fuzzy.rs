@@ -0,0 +1,106 @@
+/// Scores `candidate` as an ordered subsequence match of `query`.
+///
+/// Every query character must appear in `candidate` in order; returns
+/// `None` otherwise. Matches at word boundaries (start of name, after
+/// `-`/`_`/`.`, or a camelCase hump) and runs of consecutive matches score
+/// higher, while a large gap before the first match is penalized.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let cand_chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+
+    let mut total = 0i64;
+    let mut qi = 0;
+    let mut consecutive = 0i64;
+    let mut first_match = None;
+
+    for (ci, &c) in cand_chars.iter().enumerate() {
+        if qi == query_chars.len() {
+            break;
+        }
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        if lower != query_chars[qi] {
+            consecutive = 0;
+            continue;
+        }
+        first_match.get_or_insert(ci);
+        let mut bonus = 10;
+        if is_boundary(&cand_chars, ci) {
+            bonus += 15;
+        }
+        bonus += consecutive * 10;
+        total += bonus;
+        consecutive += 1;
+        qi += 1;
+    }
+
+    if qi < query_chars.len() {
+        return None;
+    }
+
+    total -= first_match.unwrap_or(0) as i64;
+    Some(total)
+}
+
+fn is_boundary(chars: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let prev = chars[idx - 1];
+    let cur = chars[idx];
+    matches!(prev, '-' | '_' | '.' | '/') || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+/// Returns the index of the best-scoring candidate, or `None` if nothing
+/// matches `query` as a subsequence. Ties keep the earlier candidate.
+pub fn best_match<'a>(candidates: impl Iterator<Item = &'a str>, query: &str) -> Option<usize> {
+    let mut best: Option<(usize, i64)> = None;
+    for (idx, candidate) in candidates.enumerate() {
+        if let Some(s) = score(query, candidate) {
+            if best.is_none_or(|(_, best_score)| s > best_score) {
+                best = Some((idx, s));
+            }
+        }
+    }
+    best.map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything_with_score_zero() {
+        assert_eq!(score("", "anything"), Some(0));
+        assert_eq!(score("", ""), Some(0));
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(score("xyz", "readme.md"), None);
+    }
+
+    #[test]
+    fn word_boundary_match_scores_higher_than_mid_word() {
+        // "m" matches the boundary right after "_" in "foo_main.rs", and
+        // the non-boundary "m" in "forum.rs"; the boundary hit should win.
+        let boundary = score("m", "foo_main.rs").unwrap();
+        let mid_word = score("m", "forum.rs").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn best_match_picks_the_highest_scoring_candidate() {
+        let candidates = ["forum.rs", "foo_main.rs", "readme.md"];
+        assert_eq!(best_match(candidates.into_iter(), "m"), Some(1));
+    }
+
+    #[test]
+    fn best_match_keeps_the_earlier_candidate_on_ties() {
+        let candidates = ["a.txt", "b.txt"];
+        assert_eq!(best_match(candidates.into_iter(), ""), Some(0));
+    }
+}
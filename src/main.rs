@@ -1,9 +1,9 @@
 use std::{
-    cmp::Ordering,
+    collections::HashSet,
     env,
     fs::{self, DirEntry, OpenOptions},
     io::{self, Write},
-    path::Path,
+    path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant},
 };
@@ -13,6 +13,21 @@ use clap::{arg, command};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::{prelude::*, widgets::*};
 
+mod config;
+mod fuzzy;
+mod mounts;
+mod ops;
+mod preview;
+mod sort;
+mod watch;
+use config::{Action, Config};
+use mounts::Mount;
+use ops::{Op, Task};
+use preview::{Highlighter, Preview, PreviewContent};
+use sort::{Sort, SortConfig};
+use watch::DirWatcher;
+
+#[derive(Debug)]
 struct StatefulList<T> {
     state: ListState,
     items: Vec<T>,
@@ -35,6 +50,9 @@ impl<T> StatefulList<T> {
     }
 
     fn next(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
         let i = self.state.selected().unwrap_or(0);
         if i < self.items.len() - 1 {
             self.state.select(Some(i + 1))
@@ -53,35 +71,28 @@ impl<T> StatefulList<T> {
     }
 
     fn last(&mut self) {
-        self.state.select(Some(self.items.len() - 1));
+        if !self.items.is_empty() {
+            self.state.select(Some(self.items.len() - 1));
+        }
     }
 }
 
-fn read_dir_sorted(path: &Path, show_hidden: bool) -> Vec<DirEntry> {
+fn read_dir_sorted(path: &Path, show_hidden: bool, sort_config: SortConfig) -> Vec<DirEntry> {
     match fs::read_dir(path) {
         Ok(entries) => {
             let mut entries = entries
                 .flatten()
                 .filter(|x| {
-                    !x.path().symlink_metadata().unwrap().is_symlink()
+                    let Ok(meta) = x.path().symlink_metadata() else {
+                        // Vanished between read_dir's snapshot and here (e.g. a
+                        // concurrent download or build); just drop it.
+                        return false;
+                    };
+                    !meta.is_symlink()
                         && (show_hidden || !x.file_name().to_string_lossy().starts_with('.'))
                 })
                 .collect::<Vec<_>>();
-            entries.sort_by(|a, b| {
-                let a = a.path();
-                let b = b.path();
-                let a_name = a.file_name().unwrap().to_string_lossy();
-                let b_name = b.file_name().unwrap().to_string_lossy();
-                if a.is_dir() && b.is_dir() {
-                    a_name.cmp(&b_name)
-                } else if a.is_dir() && !b.is_dir() {
-                    Ordering::Less
-                } else if !a.is_dir() && b.is_dir() {
-                    Ordering::Greater
-                } else {
-                    a_name.cmp(&b_name)
-                }
-            });
+            sort::sort_entries(&mut entries, sort_config);
             entries
         }
         Err(_) => vec![],
@@ -91,81 +102,377 @@ fn read_dir_sorted(path: &Path, show_hidden: bool) -> Vec<DirEntry> {
 #[derive(Debug)]
 enum Mode {
     Normal,
-    Find,
+    /// `query` accumulates across keystrokes; `origin` is the selection to
+    /// restore if the user cancels with Esc.
+    Find { query: String, origin: Option<usize> },
+    /// Awaiting y/n confirmation before sending `paths` to the trash.
+    Confirm { paths: Vec<PathBuf> },
+    /// Jump view: a selectable list of mounted filesystems; Enter calls
+    /// `App::enter` with the highlighted mount point.
+    Mounts(StatefulList<Mount>),
 }
 
-struct App {
+/// What the right column is currently showing: the one-level-deeper
+/// directory listing `update_right` has always produced, or a preview of
+/// the selected file.
+enum RightPane {
+    Entries(StatefulList<DirEntry>),
+    Preview(Preview),
+}
+
+/// One working directory's worth of panes. `App` holds a `Vec<Tab>` so
+/// several directories can be browsed at once, switching between them the
+/// way a browser switches between tabs.
+struct Tab {
+    path: PathBuf,
     left: StatefulList<DirEntry>,
     center: StatefulList<DirEntry>,
-    right: StatefulList<DirEntry>,
+    right: RightPane,
+}
+
+impl Tab {
+    fn at(path: PathBuf, show_hidden: bool, sort_config: SortConfig) -> Tab {
+        let (left, center) = Tab::panes_for(&path, show_hidden, sort_config, None);
+        Tab {
+            path,
+            left,
+            center,
+            right: RightPane::Entries(StatefulList::with_items(Vec::new(), None)),
+        }
+    }
+
+    /// Builds the left (parent listing, selection on `path` itself) and
+    /// center (`path`'s own listing) panes for a tab rooted at `path`.
+    /// `center_selected` is matched by path in the freshly read center
+    /// listing, defaulting to index 0 when absent or no longer present.
+    fn panes_for(
+        path: &Path,
+        show_hidden: bool,
+        sort_config: SortConfig,
+        center_selected: Option<&Path>,
+    ) -> (StatefulList<DirEntry>, StatefulList<DirEntry>) {
+        let left = if let Some(parent) = path.parent() {
+            read_dir_sorted(parent, show_hidden, sort_config)
+        } else {
+            Vec::new()
+        };
+        let left_selected = left.iter().position(|x| x.path() == path);
+        let center = read_dir_sorted(path, show_hidden, sort_config);
+        let center_selected = match center_selected {
+            Some(p) => center.iter().position(|x| x.path() == p).or(Some(0)),
+            None => Some(0),
+        };
+        (
+            StatefulList::with_items(left, left_selected),
+            StatefulList::with_items(center, center_selected),
+        )
+    }
+}
+
+struct App {
+    tabs: Vec<Tab>,
+    active: usize,
     mode: Mode,
     show_hidden: bool,
     show_icons: bool,
-    accent: Color,
+    config: Config,
+    highlighter: Highlighter,
+    /// `None` when the watcher backend failed to start (e.g. inotify
+    /// instances exhausted); the app still runs, just without live-refresh.
+    watcher: Option<DirWatcher>,
+    sort_config: SortConfig,
+    /// Entries marked in the center pane for a bulk yank/cut/delete.
+    marked: HashSet<PathBuf>,
+    /// Paths staged by yank/cut, paste-able into the current directory.
+    clipboard: Option<(Vec<PathBuf>, Op)>,
+    /// The in-flight copy/move/delete, if any; polled each tick.
+    task: Option<Task>,
+    /// The most recent failure worth telling the user about, shown in the
+    /// status line until the next action replaces or clears it.
+    last_error: Option<String>,
 }
 
 impl App {
-    fn new(show_hidden: bool, show_icons: bool, accent: Color) -> App {
+    fn new(show_hidden: bool, show_icons: bool, sort_config: SortConfig, config: Config) -> App {
         let current_dir = env::current_dir().unwrap();
-        let left = if let Some(parent) = current_dir.parent() {
-            read_dir_sorted(parent, show_hidden)
-        } else {
-            Vec::new()
-        };
-        let center = read_dir_sorted(&current_dir, show_hidden);
-        let right = if let Some(selected) = center.first() {
-            read_dir_sorted(&current_dir.join(selected.path()), show_hidden)
-        } else {
-            Vec::new()
+        let (watcher, last_error) = match DirWatcher::new() {
+            Ok(watcher) => (Some(watcher), None),
+            Err(e) => (None, Some(format!("live-refresh disabled: {e}"))),
         };
-        let left_selected = left.iter().position(|x| x.path().eq(current_dir.as_path()));
-
-        App {
-            left: StatefulList::with_items(left, left_selected),
-            center: StatefulList::with_items(center, Some(0)),
-            right: StatefulList::with_items(right, None),
+        let mut app = App {
+            tabs: vec![Tab::at(current_dir, show_hidden, sort_config)],
+            active: 0,
             mode: Mode::Normal,
             show_hidden,
             show_icons,
-            accent,
+            config,
+            highlighter: Highlighter::new(),
+            watcher,
+            sort_config,
+            marked: HashSet::new(),
+            clipboard: None,
+            task: None,
+            last_error,
+        };
+        app.update_right();
+        app
+    }
+
+    /// The paths an operation should act on: the marked set if non-empty,
+    /// otherwise just the current selection.
+    fn marked_or_selected(&self) -> Vec<PathBuf> {
+        if self.marked.is_empty() {
+            self.tab()
+                .center
+                .selected()
+                .map(DirEntry::path)
+                .into_iter()
+                .collect()
+        } else {
+            self.marked.iter().cloned().collect()
+        }
+    }
+
+    /// Re-reads the active tab's panes, e.g. after a paste or delete task
+    /// finishes changing the directory out from under them.
+    fn refresh_active_tab(&mut self) {
+        let current_dir = env::current_dir().unwrap();
+        if let Some(parent) = current_dir.parent() {
+            self.refresh_pane(parent);
+        }
+        self.refresh_pane(&current_dir);
+    }
+
+    fn tab(&self) -> &Tab {
+        &self.tabs[self.active]
+    }
+
+    fn tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active]
+    }
+
+    /// Duplicates the active tab's path into a new tab and switches to it.
+    fn new_tab(&mut self) {
+        let path = self.tab().path.clone();
+        self.tabs
+            .insert(self.active + 1, Tab::at(path, self.show_hidden, self.sort_config));
+        self.active += 1;
+        self.update_right();
+    }
+
+    /// Closes the active tab, refusing to close the last one, and restores
+    /// the process CWD to whichever tab becomes active.
+    fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            return;
+        }
+        self.tabs.remove(self.active);
+        if self.active == self.tabs.len() {
+            self.active -= 1;
+        }
+        let path = self.tab().path.clone();
+        self.chdir_active_tab(&path);
+    }
+
+    /// Cycles the active tab by `delta` (e.g. 1 or -1), wrapping around,
+    /// and restores the process CWD to match.
+    fn cycle_tab(&mut self, delta: isize) {
+        let len = self.tabs.len() as isize;
+        self.active = (self.active as isize + delta).rem_euclid(len) as usize;
+        let path = self.tab().path.clone();
+        self.chdir_active_tab(&path);
+    }
+
+    /// Restores the process CWD to `path`, the active tab's own directory.
+    /// Only the *active* tab's panes are kept live by `sync_watches`, so a
+    /// background tab's directory can be deleted, unmounted, or renamed
+    /// while it's inactive; walk up to the nearest still-accessible
+    /// ancestor instead of panicking on `chdir`, updating the tab to match
+    /// and leaving a note in `last_error`.
+    fn chdir_active_tab(&mut self, path: &Path) {
+        let mut candidate = path.to_path_buf();
+        while env::set_current_dir(&candidate).is_err() {
+            let Some(parent) = candidate.parent() else {
+                self.last_error = Some(format!("can't access {}", path.display()));
+                return;
+            };
+            candidate = parent.to_path_buf();
+        }
+        if candidate != path {
+            self.last_error = Some(format!(
+                "{} is no longer accessible, showing {} instead",
+                path.display(),
+                candidate.display()
+            ));
+            let (left, center) = Tab::panes_for(&candidate, self.show_hidden, self.sort_config, None);
+            let tab = self.tab_mut();
+            tab.path = candidate;
+            tab.left = left;
+            tab.center = center;
         }
+        self.update_right();
     }
 
     fn enter(&mut self, path: &Path) {
-        env::set_current_dir(path).unwrap();
-        let left = read_dir_sorted(path.parent().unwrap(), self.show_hidden);
-        let center = read_dir_sorted(path, self.show_hidden);
-        let left_selected = left.iter().position(|x| x.path().eq(path));
-        self.left = StatefulList::with_items(left, left_selected);
-        self.center = StatefulList::with_items(center, Some(0));
+        if let Err(e) = env::set_current_dir(path) {
+            self.last_error = Some(format!("can't open {}: {e}", path.display()));
+            return;
+        }
+        self.last_error = None;
+        let (left, center) = Tab::panes_for(path, self.show_hidden, self.sort_config, None);
+        let tab = self.tab_mut();
+        tab.path = path.to_path_buf();
+        tab.left = left;
+        tab.center = center;
     }
 
     fn leave(&mut self) {
         let leaving = env::current_dir().unwrap();
-        if let Some(path) = leaving.parent() {
-            env::set_current_dir(path).unwrap();
-            let left = if let Some(parent) = path.parent() {
-                read_dir_sorted(parent, self.show_hidden)
-            } else {
-                Vec::new()
+        let Some(start) = leaving.parent() else {
+            return;
+        };
+        let mut candidate = start.to_path_buf();
+        while env::set_current_dir(&candidate).is_err() {
+            let Some(parent) = candidate.parent() else {
+                self.last_error = Some(format!("can't access {}", candidate.display()));
+                return;
             };
-            let center = read_dir_sorted(path, self.show_hidden);
-            let left_selected = left.iter().position(|x| x.path().eq(path));
-            let center_selcted = center.iter().position(|x| x.path().eq(leaving.as_path()));
-            self.left = StatefulList::with_items(left, left_selected);
-            self.center = StatefulList::with_items(center, center_selcted);
+            candidate = parent.to_path_buf();
+        }
+        if candidate != start {
+            self.last_error = Some(format!(
+                "{} is no longer accessible, showing {} instead",
+                start.display(),
+                candidate.display()
+            ));
         }
+        let (left, center) = Tab::panes_for(&candidate, self.show_hidden, self.sort_config, Some(&leaving));
+        let tab = self.tab_mut();
+        tab.path = candidate;
+        tab.left = left;
+        tab.center = center;
     }
 
     fn update_right(&mut self) {
         let current_dir = env::current_dir().unwrap();
-        if let Some(selected) = self.center.selected() {
-            let right = read_dir_sorted(&current_dir.join(selected.path()), self.show_hidden);
-            self.right = StatefulList::with_items(right, None);
+        if let Some(selected) = self.tab().center.selected() {
+            let path = current_dir.join(selected.path());
+            self.tab_mut().right = if path.is_dir() {
+                let entries = read_dir_sorted(&path, self.show_hidden, self.sort_config);
+                RightPane::Entries(StatefulList::with_items(entries, None))
+            } else {
+                RightPane::Preview(Preview::load(&path))
+            };
+        }
+        self.sync_watches();
+    }
+
+    /// Registers the directories backing the active tab's three panes with
+    /// the FS watcher, dropping any pane that navigation has since left
+    /// behind (including panes from tabs that are no longer active).
+    fn sync_watches(&mut self) {
+        let current_dir = env::current_dir().unwrap();
+        let mut dirs = vec![current_dir.clone()];
+        if let Some(parent) = current_dir.parent() {
+            dirs.push(parent.to_path_buf());
+        }
+        if let RightPane::Entries(_) = self.tab().right {
+            if let Some(selected) = self.tab().center.selected() {
+                dirs.push(current_dir.join(selected.path()));
+            }
+        }
+        if let Some(watcher) = &mut self.watcher {
+            watcher.set_watched(&dirs);
+        }
+    }
+
+    /// Re-reads whichever pane of the active tab is backed by `changed`,
+    /// preserving the current selection by matching on path rather than
+    /// index.
+    fn refresh_pane(&mut self, changed: &Path) {
+        let current_dir = env::current_dir().unwrap();
+        if Some(changed) == current_dir.parent() {
+            let selected_path = self.tab().left.selected().map(DirEntry::path);
+            let entries = read_dir_sorted(changed, self.show_hidden, self.sort_config);
+            let idx = selected_path.and_then(|p| entries.iter().position(|x| x.path() == p));
+            self.tab_mut().left = StatefulList::with_items(entries, idx);
+        } else if changed == current_dir {
+            let selected_path = self.tab().center.selected().map(DirEntry::path);
+            let entries = read_dir_sorted(changed, self.show_hidden, self.sort_config);
+            let idx = selected_path
+                .and_then(|p| entries.iter().position(|x| x.path() == p))
+                .or(Some(0));
+            self.tab_mut().center = StatefulList::with_items(entries, idx);
+            self.update_right();
+        } else if let RightPane::Entries(list) = &self.tab().right {
+            let right_dir = self
+                .tab()
+                .center
+                .selected()
+                .map(|selected| current_dir.join(selected.path()));
+            if right_dir.as_deref() == Some(changed) {
+                let selected_path = list.selected().map(DirEntry::path);
+                let entries = read_dir_sorted(changed, self.show_hidden, self.sort_config);
+                let idx = selected_path.and_then(|p| entries.iter().position(|x| x.path() == p));
+                self.tab_mut().right = RightPane::Entries(StatefulList::with_items(entries, idx));
+            }
+        }
+    }
+
+    /// Re-ranks `center` against the current `Mode::Find` query and jumps
+    /// to the best subsequence match, if any.
+    fn apply_find(&mut self) {
+        let Mode::Find { query, origin } = &self.mode else {
+            return;
+        };
+        let origin = *origin;
+        let query = query.clone();
+        if query.is_empty() {
+            // An empty query scores every candidate Some(0), so best_match
+            // would always snap back to index 0; leave the selection where
+            // Find started instead.
+            self.tab_mut().center.state.select(origin);
+            self.update_right();
+            return;
+        }
+        let names: Vec<String> = self
+            .tab()
+            .center
+            .items
+            .iter()
+            .map(|x| x.file_name().to_string_lossy().into_owned())
+            .collect();
+        if let Some(idx) = fuzzy::best_match(names.iter().map(String::as_str), &query) {
+            self.tab_mut().center.state.select(Some(idx));
+        }
+        self.update_right();
+    }
+
+    /// Re-sorts all three panes in place under the current `sort_config`,
+    /// keeping each pane's selection on the same entry.
+    fn re_sort(&mut self) {
+        let sort_config = self.sort_config;
+        let tab = self.tab_mut();
+        resort_list(&mut tab.left, sort_config);
+        resort_list(&mut tab.center, sort_config);
+        if let RightPane::Entries(list) = &mut tab.right {
+            resort_list(list, sort_config);
         }
     }
 }
 
+fn resort_list(list: &mut StatefulList<DirEntry>, sort_config: SortConfig) {
+    let selected_path = list.selected().map(DirEntry::path);
+    sort::sort_entries(&mut list.items, sort_config);
+    let idx = selected_path.and_then(|p| list.items.iter().position(|x| x.path() == p));
+    list.state.select(idx);
+}
+
+/// There's no async runtime here, so we approximate `select!`-ing over the
+/// terminal event stream and the FS watcher channel by polling crossterm
+/// in short slices and checking for a debounced FS event between them.
+const FS_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut app: App,
@@ -175,87 +482,233 @@ fn run_app<B: Backend>(
     loop {
         terminal.draw(|f| ui(f, &mut app))?;
 
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        let timeout = tick_rate
+            .saturating_sub(last_tick.elapsed())
+            .min(FS_POLL_INTERVAL);
         if crossterm::event::poll(timeout)? {
             match event::read()? {
                 Event::Key(key) => {
                     if key.kind == KeyEventKind::Press {
                         if matches!(app.mode, Mode::Normal) {
+                            if let Some(action) = app.config.keymap.get(key) {
+                                match action {
+                                    Action::Quit => return Ok(()),
+                                    Action::Down => {
+                                        app.tab_mut().center.next();
+                                        app.update_right();
+                                    }
+                                    Action::HalfPageDown => {
+                                        for _ in 0..app.config.half_page_step {
+                                            app.tab_mut().center.next();
+                                        }
+                                        app.update_right();
+                                    }
+                                    Action::Up => {
+                                        app.tab_mut().center.previous();
+                                        app.update_right();
+                                    }
+                                    Action::HalfPageUp => {
+                                        for _ in 0..app.config.half_page_step {
+                                            app.tab_mut().center.previous();
+                                        }
+                                        app.update_right();
+                                    }
+                                    Action::ScrollPreviewDown => {
+                                        let step = app.config.half_page_step as isize;
+                                        if let RightPane::Preview(preview) = &mut app.tab_mut().right {
+                                            preview.scroll(step);
+                                        }
+                                    }
+                                    Action::ScrollPreviewUp => {
+                                        let step = -(app.config.half_page_step as isize);
+                                        if let RightPane::Preview(preview) = &mut app.tab_mut().right {
+                                            preview.scroll(step);
+                                        }
+                                    }
+                                    Action::First => app.tab_mut().center.first(),
+                                    Action::Last => app.tab_mut().center.last(),
+                                    Action::Leave => {
+                                        app.leave();
+                                        app.update_right();
+                                    }
+                                    Action::Enter => {
+                                        let selected =
+                                            app.tab().center.selected().map(DirEntry::path);
+                                        if let Some(path) = selected {
+                                            if path.is_dir() {
+                                                app.enter(&path);
+                                                app.update_right();
+                                            }
+                                        }
+                                    }
+                                    Action::Find => {
+                                        app.mode = Mode::Find {
+                                            query: String::new(),
+                                            origin: app.tab_mut().center.state.selected(),
+                                        }
+                                    }
+                                    Action::ToggleHidden => {
+                                        app.show_hidden = !app.show_hidden;
+                                        app.refresh_active_tab();
+                                    }
+                                    Action::CycleSort => {
+                                        app.sort_config.sort = app.sort_config.sort.next();
+                                        app.re_sort();
+                                    }
+                                    Action::ToggleReverse => {
+                                        app.sort_config.reverse = !app.sort_config.reverse;
+                                        app.re_sort();
+                                    }
+                                    Action::ToggleDirsFirst => {
+                                        app.sort_config.dirs_first = !app.sort_config.dirs_first;
+                                        app.re_sort();
+                                    }
+                                    Action::Mounts => {
+                                        app.mode = Mode::Mounts(StatefulList::with_items(
+                                            mounts::list_mounts(),
+                                            Some(0),
+                                        ));
+                                    }
+                                    Action::NewTab => app.new_tab(),
+                                    Action::CloseTab => app.close_tab(),
+                                    Action::NextTab => app.cycle_tab(1),
+                                    Action::PrevTab => app.cycle_tab(-1),
+                                    Action::Mark => {
+                                        if let Some(selected) = app.tab().center.selected() {
+                                            let path = selected.path();
+                                            if !app.marked.remove(&path) {
+                                                app.marked.insert(path);
+                                            }
+                                        }
+                                    }
+                                    Action::Yank => {
+                                        let paths = app.marked_or_selected();
+                                        if !paths.is_empty() {
+                                            app.clipboard = Some((paths, Op::Copy));
+                                        }
+                                    }
+                                    Action::Cut => {
+                                        let paths = app.marked_or_selected();
+                                        if !paths.is_empty() {
+                                            app.clipboard = Some((paths, Op::Move));
+                                        }
+                                    }
+                                    Action::Paste => {
+                                        if app.task.is_some() {
+                                            app.last_error =
+                                                Some("a task is already running".into());
+                                        } else if let Some((paths, op)) = app.clipboard.take() {
+                                            let dest_dir = env::current_dir().unwrap();
+                                            app.task =
+                                                Some(Task::spawn(op, paths, Some(dest_dir)));
+                                            app.last_error = None;
+                                        }
+                                    }
+                                    Action::Delete => {
+                                        let paths = app.marked_or_selected();
+                                        if !paths.is_empty() {
+                                            app.mode = Mode::Confirm { paths };
+                                        }
+                                    }
+                                    Action::Select => {
+                                        if let Some(selected) = app.tab().center.selected() {
+                                            let path = selected.path();
+                                            if path.is_dir() {
+                                                fs::write("/tmp/seldir", path.to_str().unwrap())?;
+                                                return Ok(());
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else if matches!(app.mode, Mode::Confirm { .. }) {
                             match key.code {
-                                KeyCode::Esc => return Ok(()),
-                                KeyCode::Down | KeyCode::Char('j') => {
-                                    app.center.next();
-                                    app.update_right();
+                                KeyCode::Char('y') | KeyCode::Enter => {
+                                    let Mode::Confirm { paths } =
+                                        std::mem::replace(&mut app.mode, Mode::Normal)
+                                    else {
+                                        unreachable!()
+                                    };
+                                    if app.task.is_some() {
+                                        app.last_error =
+                                            Some("a task is already running".into());
+                                    } else {
+                                        app.task = Some(Task::spawn(Op::Delete, paths, None));
+                                        app.last_error = None;
+                                    }
                                 }
-                                KeyCode::Char('J') => {
-                                    app.center.next();
-                                    app.center.next();
-                                    app.center.next();
-                                    app.center.next();
-                                    app.center.next();
-                                    app.update_right();
+                                KeyCode::Char('n') | KeyCode::Esc => app.mode = Mode::Normal,
+                                _ => {}
+                            }
+                        } else if matches!(app.mode, Mode::Mounts(_)) {
+                            match key.code {
+                                KeyCode::Down | KeyCode::Char('j') => {
+                                    if let Mode::Mounts(list) = &mut app.mode {
+                                        list.next();
+                                    }
                                 }
                                 KeyCode::Up | KeyCode::Char('k') => {
-                                    app.center.previous();
-                                    app.update_right();
-                                }
-                                KeyCode::Char('K') => {
-                                    app.center.previous();
-                                    app.center.previous();
-                                    app.center.previous();
-                                    app.center.previous();
-                                    app.center.previous();
-                                    app.update_right();
-                                }
-                                KeyCode::Char('g') => app.center.first(),
-                                KeyCode::Char('G') => app.center.last(),
-                                KeyCode::Left | KeyCode::Char('h') => {
-                                    app.leave();
-                                    app.update_right();
-                                }
-                                KeyCode::Right | KeyCode::Char('l') => {
-                                    if let Some(selected) = app.center.selected() {
-                                        if selected.path().is_dir() {
-                                            app.enter(&selected.path());
-                                            app.update_right();
-                                        }
+                                    if let Mode::Mounts(list) = &mut app.mode {
+                                        list.previous();
                                     }
                                 }
-                                KeyCode::Char('f') => app.mode = Mode::Find,
-                                KeyCode::Char('q') | KeyCode::Enter => {
-                                    if let Some(selected) = app.center.selected() {
-                                        let path = selected.path();
-                                        if path.is_dir() {
-                                            fs::write("/tmp/seldir", path.to_str().unwrap())?;
-                                            return Ok(());
-                                        }
+                                KeyCode::Enter => {
+                                    let Mode::Mounts(list) =
+                                        std::mem::replace(&mut app.mode, Mode::Normal)
+                                    else {
+                                        unreachable!()
+                                    };
+                                    if let Some(mount) = list.selected().cloned() {
+                                        app.enter(&mount.mount_point);
+                                        app.update_right();
                                     }
                                 }
+                                KeyCode::Esc => app.mode = Mode::Normal,
                                 _ => {}
                             }
                         } else {
-                            if let KeyCode::Char(c) = key.code {
-                                if let Mode::Find = app.mode {
-                                    if let Some(idx) = app.center.items.iter().position(|x| {
-                                        x.file_name()
-                                            .into_string()
-                                            .unwrap()
-                                            .to_lowercase()
-                                            .starts_with(c)
-                                    }) {
-                                        app.center.state.select(Some(idx));
+                            match key.code {
+                                KeyCode::Char(c) => {
+                                    if let Mode::Find { query, .. } = &mut app.mode {
+                                        query.push(c);
+                                    }
+                                    app.apply_find();
+                                }
+                                KeyCode::Backspace => {
+                                    if let Mode::Find { query, .. } = &mut app.mode {
+                                        query.pop();
                                     }
+                                    app.apply_find();
+                                }
+                                KeyCode::Enter => {
+                                    app.mode = Mode::Normal;
+                                }
+                                KeyCode::Esc => {
+                                    if let Mode::Find { origin, .. } = app.mode {
+                                        app.tab_mut().center.state.select(origin);
+                                    }
+                                    app.mode = Mode::Normal;
                                     app.update_right();
                                 }
+                                _ => {}
                             }
-
-                            app.mode = Mode::Normal;
                         }
                     }
                 }
                 Event::Resize(_, _) => terminal.autoresize()?,
                 _ => {}
             }
+        } else if let Some(changed) = app.watcher.as_ref().and_then(DirWatcher::try_recv) {
+            app.refresh_pane(&changed);
+        }
+        if app.task.as_mut().is_some_and(Task::poll) {
+            let task = app.task.take().unwrap();
+            if let Some(error) = task.error {
+                app.last_error = Some(format!("{} failed: {error}", task.op.label()));
+            } else {
+                app.marked.clear();
+            }
+            app.refresh_active_tab();
         }
         if last_tick.elapsed() >= tick_rate {
             last_tick = Instant::now();
@@ -263,37 +716,131 @@ fn run_app<B: Backend>(
     }
 }
 
-fn into_list_item<'a>(dir_entry: &DirEntry, accent: Color, show_icons: bool) -> ListItem<'a> {
-    let dir_icon = if show_icons { "   " } else { " " };
-    let file_icon = if show_icons { "   " } else { " " };
-    if dir_entry.metadata().unwrap().is_dir() {
-        ListItem::new(dir_icon.to_owned() + dir_entry.file_name().to_str().unwrap())
-            .style(Style::default().fg(accent))
+fn into_list_item<'a>(
+    dir_entry: &DirEntry,
+    accent: Color,
+    show_icons: bool,
+    marked: bool,
+    dir_icon: &str,
+    file_icon: &str,
+) -> ListItem<'a> {
+    let dir_icon = if show_icons { format!(" {dir_icon}  ") } else { " ".to_owned() };
+    let file_icon = if show_icons { format!(" {file_icon}  ") } else { " ".to_owned() };
+    let mark = if marked { "*" } else { " " };
+    let name = dir_entry.file_name().to_string_lossy().into_owned();
+    // metadata() can fail if the entry vanished since the listing was taken
+    // (e.g. a concurrent download or build); fall back to treating it as a
+    // plain file rather than panicking mid-render.
+    let item = if dir_entry.metadata().map(|m| m.is_dir()).unwrap_or(false) {
+        ListItem::new(format!("{mark}{dir_icon}{name}")).style(Style::default().fg(accent))
     } else {
-        ListItem::new(file_icon.to_owned() + dir_entry.file_name().to_str().unwrap())
+        ListItem::new(format!("{mark}{file_icon}{name}"))
+    };
+    if marked {
+        item.style(Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        item
     }
 }
 
+/// Renders one `Mode::Mounts` row: mount point, a compact usage bar, and
+/// human-readable used/total sizes, in roughly `df -h`'s register.
+fn mount_list_item<'a>(mount: &Mount, accent: Color) -> ListItem<'a> {
+    const BAR_WIDTH: usize = 10;
+    let filled = ((mount.usage_fraction().clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize)
+        .min(BAR_WIDTH);
+    let bar = format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+    let label = format!(
+        " {}  {} {bar} {}/{} ({} free)  {}",
+        mount.device,
+        mount.mount_point.display(),
+        mounts::human_size(mount.used),
+        mounts::human_size(mount.total),
+        mounts::human_size(mount.available),
+        mount.fs_type,
+    );
+    ListItem::new(label).style(Style::default().fg(accent))
+}
+
 fn ui(f: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ])
         .split(f.size());
 
+    let tab_spans: Vec<Span> = app
+        .tabs
+        .iter()
+        .enumerate()
+        .map(|(i, tab)| {
+            let name = tab
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "/".to_owned());
+            let label = format!(" {} {name} ", i + 1);
+            if i == app.active {
+                Span::styled(label, Style::default().fg(app.config.theme.accent).reversed())
+            } else {
+                Span::raw(label)
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(tab_spans)), chunks[0]);
+
     let current_dir = env::current_dir().unwrap();
     let mut path = current_dir.to_str().unwrap().to_owned();
     if !path.ends_with('/') {
         path += "/";
     }
-    let selection = if let Some(selected) = app.center.selected() {
+    let selection = if let Some(selected) = app.tab().center.selected() {
         Span::from(selected.file_name().into_string().unwrap())
     } else {
         Span::default()
     };
-    f.render_widget(
-        Paragraph::new(Line::from(vec![Span::raw(path), selection])),
-        chunks[0],
-    );
+    let mut spans = vec![Span::raw(path), selection];
+    spans.push(Span::raw(format!(
+        "  [{}{}{}]",
+        app.sort_config.sort.label(),
+        if app.sort_config.reverse { " rev" } else { "" },
+        if app.sort_config.dirs_first {
+            " dirs-first"
+        } else {
+            ""
+        },
+    )));
+    if let Mode::Find { query, .. } = &app.mode {
+        spans.push(Span::raw("  find: "));
+        spans.push(Span::styled(query.clone(), Style::default().fg(app.config.theme.accent)));
+    }
+    if let Mode::Confirm { paths } = &app.mode {
+        spans.push(Span::styled(
+            format!("  delete {} item(s)? (y/n)", paths.len()),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    if let Mode::Mounts(_) = &app.mode {
+        spans.push(Span::raw("  filesystems: enter to jump, esc to cancel"));
+    }
+    if let Some(task) = &app.task {
+        spans.push(Span::raw(format!(
+            "  {} {}/{} ({}/{})",
+            task.op.label(),
+            task.done,
+            task.total,
+            mounts::human_size(task.bytes_done),
+            mounts::human_size(task.bytes_total),
+        )));
+    } else if let Some(error) = &app.last_error {
+        spans.push(Span::styled(format!("  {error}"), Style::default().fg(Color::Red)));
+    } else if !app.marked.is_empty() {
+        spans.push(Span::raw(format!("  {} marked", app.marked.len())));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), chunks[1]);
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -302,38 +849,104 @@ fn ui(f: &mut Frame, app: &mut App) {
             Constraint::Percentage(30),
             Constraint::Percentage(30),
         ])
-        .split(chunks[1]);
+        .split(chunks[2]);
 
+    let dir_icon = app.config.theme.dir_icon.clone();
+    let file_icon = app.config.theme.file_icon.clone();
     let left: Vec<ListItem> = app
+        .tab()
         .left
         .items
         .iter()
-        .map(|x| into_list_item(x, app.accent, app.show_icons))
-        .collect();
-    let center: Vec<ListItem> = app
-        .center
-        .items
-        .iter()
-        .map(|x| into_list_item(x, app.accent, app.show_icons))
-        .collect();
-    let right: Vec<ListItem> = app
-        .right
-        .items
-        .iter()
-        .map(|x| into_list_item(x, app.accent, app.show_icons))
+        .map(|x| {
+            into_list_item(
+                x,
+                app.config.theme.accent,
+                app.show_icons,
+                app.marked.contains(&x.path()),
+                &dir_icon,
+                &file_icon,
+            )
+        })
         .collect();
-
     let left = List::new(left)
-        .highlight_style(Style::default().reversed())
-        .block(Block::default().padding(Padding::new(0, 1, 0, 0)));
-    let center = List::new(center)
-        .highlight_style(Style::default().reversed())
+        .highlight_style(app.config.theme.highlight)
         .block(Block::default().padding(Padding::new(0, 1, 0, 0)));
-    let right = List::new(right).highlight_style(Style::default().reversed());
 
-    f.render_stateful_widget(left, chunks[0], &mut app.left.state);
-    f.render_stateful_widget(center, chunks[1], &mut app.center.state);
-    f.render_stateful_widget(right, chunks[2], &mut app.right.state);
+    let active = app.active;
+    f.render_stateful_widget(left, chunks[0], &mut app.tabs[active].left.state);
+
+    if let Mode::Mounts(list) = &mut app.mode {
+        let center: Vec<ListItem> = list
+            .items
+            .iter()
+            .map(|m| mount_list_item(m, app.config.theme.accent))
+            .collect();
+        let center = List::new(center)
+            .highlight_style(app.config.theme.highlight)
+            .block(Block::default().padding(Padding::new(0, 1, 0, 0)));
+        f.render_stateful_widget(center, chunks[1], &mut list.state);
+    } else {
+        let center: Vec<ListItem> = app
+            .tabs[active]
+            .center
+            .items
+            .iter()
+            .map(|x| {
+                into_list_item(
+                    x,
+                    app.config.theme.accent,
+                    app.show_icons,
+                    app.marked.contains(&x.path()),
+                    &dir_icon,
+                    &file_icon,
+                )
+            })
+            .collect();
+        let center = List::new(center)
+            .highlight_style(app.config.theme.highlight)
+            .block(Block::default().padding(Padding::new(0, 1, 0, 0)));
+        f.render_stateful_widget(center, chunks[1], &mut app.tabs[active].center.state);
+    }
+
+    match &mut app.tabs[active].right {
+        RightPane::Entries(list) => {
+            let right: Vec<ListItem> = list
+                .items
+                .iter()
+                .map(|x| {
+                    into_list_item(
+                        x,
+                        app.config.theme.accent,
+                        app.show_icons,
+                        false,
+                        &dir_icon,
+                        &file_icon,
+                    )
+                })
+                .collect();
+            let right = List::new(right).highlight_style(app.config.theme.highlight);
+            f.render_stateful_widget(right, chunks[2], &mut list.state);
+        }
+        RightPane::Preview(preview) => {
+            let height = chunks[2].height as usize;
+            let top = preview.top;
+            let paragraph = match &preview.content {
+                PreviewContent::Text { lines, ext } => Paragraph::new(app.highlighter.highlight_viewport(
+                    lines,
+                    ext,
+                    top,
+                    height,
+                    &mut preview.highlight_cache,
+                )),
+                PreviewContent::Binary { len, dump } => {
+                    Paragraph::new(format!("binary file, {len} bytes\n{dump}"))
+                }
+                PreviewContent::Empty => Paragraph::new(""),
+            };
+            f.render_widget(paragraph, chunks[2]);
+        }
+    }
 }
 
 fn main() -> Result<()> {
@@ -341,14 +954,26 @@ fn main() -> Result<()> {
         .arg(arg!(-a --all "Show hidden files"))
         .arg(arg!(-i --icons "Show icons"))
         .arg(arg!(-c --color <COLOR> "Accent color"))
+        .arg(arg!(-s --sort <SORT> "Sort order: name, natural, size, modified, extension").required(false))
+        .arg(arg!(-r --reverse "Reverse the sort order"))
+        .arg(arg!(--"no-dirs-first" "Don't group directories before files"))
         .get_matches();
 
     let show_hidden = *matches.get_one::<bool>("all").unwrap();
     let show_icons = *matches.get_one::<bool>("icons").unwrap();
-    let accent = matches
-        .get_one::<String>("color")
-        .unwrap_or(&"red".to_owned())
-        .clone();
+    let mut config = Config::load();
+    if let Some(accent) = matches.get_one::<String>("color") {
+        config.theme.accent = Color::from_str(accent)?;
+    }
+    let sort = match matches.get_one::<String>("sort") {
+        Some(s) => Sort::from_str(s).map_err(|e| anyhow::anyhow!(e))?,
+        None => Sort::Name,
+    };
+    let sort_config = SortConfig {
+        sort,
+        reverse: *matches.get_one::<bool>("reverse").unwrap(),
+        dirs_first: !*matches.get_one::<bool>("no-dirs-first").unwrap(),
+    };
 
     let mut file = OpenOptions::new()
         .create(true)
@@ -368,7 +993,7 @@ fn main() -> Result<()> {
         },
     )?;
     let tick_rate = Duration::from_millis(200);
-    let app = App::new(show_hidden, show_icons, Color::from_str(&accent)?);
+    let app = App::new(show_hidden, show_icons, sort_config, config);
 
     run_app(&mut terminal, app, tick_rate)?;
 
@@ -377,3 +1002,96 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // chdir_active_tab/leave mutate the process's current directory, which
+    // is global state shared across every test binary thread; serialize the
+    // ones that touch it so they can't race each other's set_current_dir.
+    static CWD_LOCK: Mutex<()> = Mutex::new(());
+
+    fn sort_config() -> SortConfig {
+        SortConfig {
+            sort: Sort::Name,
+            reverse: false,
+            dirs_first: true,
+        }
+    }
+
+    /// Builds an `App` rooted at `path` without `App::new`'s watcher setup,
+    /// which is irrelevant to the navigation logic under test here.
+    fn test_app(path: PathBuf) -> App {
+        App {
+            tabs: vec![Tab::at(path, false, sort_config())],
+            active: 0,
+            mode: Mode::Normal,
+            show_hidden: false,
+            show_icons: false,
+            config: Config::default(),
+            highlighter: Highlighter::new(),
+            watcher: None,
+            sort_config: sort_config(),
+            marked: HashSet::new(),
+            clipboard: None,
+            task: None,
+            last_error: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("seldir2-main-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn chdir_active_tab_falls_back_to_the_nearest_existing_ancestor() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let original_cwd = env::current_dir().unwrap();
+
+        let root = temp_dir("chdir-fallback");
+        let gone = root.join("gone");
+        fs::create_dir(&gone).unwrap();
+
+        let mut app = test_app(gone.clone());
+        fs::remove_dir_all(&gone).unwrap();
+
+        app.chdir_active_tab(&gone);
+
+        assert_eq!(env::current_dir().unwrap(), root);
+        assert_eq!(app.tab().path, root);
+        assert!(app.last_error.is_some());
+
+        env::set_current_dir(original_cwd).unwrap();
+    }
+
+    #[test]
+    fn leave_selects_the_directory_we_came_from() {
+        let _guard = CWD_LOCK.lock().unwrap();
+        let original_cwd = env::current_dir().unwrap();
+
+        let root = temp_dir("leave-selection");
+        let child = root.join("child");
+        fs::create_dir(&child).unwrap();
+        fs::write(root.join("sibling.txt"), b"x").unwrap();
+
+        let mut app = test_app(child.clone());
+        env::set_current_dir(&child).unwrap();
+
+        app.leave();
+
+        assert_eq!(app.tab().path, root);
+        let selected = app
+            .tab()
+            .center
+            .selected()
+            .expect("the directory we left should still be selected");
+        assert_eq!(selected.path(), child);
+
+        env::set_current_dir(original_cwd).unwrap();
+    }
+}
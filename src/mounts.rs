@@ -0,0 +1,176 @@
+use std::{ffi::CString, mem::MaybeUninit, os::unix::ffi::OsStrExt, path::Path, path::PathBuf};
+
+/// One line of the mount table, with usage filled in via `statvfs`.
+#[derive(Debug, Clone)]
+pub struct Mount {
+    pub device: String,
+    pub mount_point: PathBuf,
+    pub fs_type: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+}
+
+impl Mount {
+    pub fn usage_fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64
+        }
+    }
+}
+
+/// Platform hook for reading the raw mount table, so a non-Linux build can
+/// plug in its own source without touching `list_mounts`.
+trait MountSource {
+    fn entries(&self) -> Vec<(String, PathBuf, String)>;
+}
+
+struct ProcMounts;
+
+impl MountSource for ProcMounts {
+    fn entries(&self) -> Vec<(String, PathBuf, String)> {
+        let Ok(contents) = std::fs::read_to_string("/proc/mounts") else {
+            return Vec::new();
+        };
+        contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let device = fields.next()?.to_owned();
+                let mount_point = PathBuf::from(unescape_octal(fields.next()?));
+                let fs_type = fields.next()?.to_owned();
+                Some((device, mount_point, fs_type))
+            })
+            .collect()
+    }
+}
+
+/// `/proc/mounts` escapes spaces, tabs, and backslashes as `\NNN` octal.
+fn unescape_octal(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let octal: String = chars.by_ref().take(3).collect();
+        match u8::from_str_radix(&octal, 8) {
+            Ok(byte) => out.push(byte as char),
+            Err(_) => {
+                out.push(c);
+                out.push_str(&octal);
+            }
+        }
+    }
+    out
+}
+
+/// Virtual filesystem types that never represent real storage, so they'd
+/// otherwise drown out the handful of disks in a typical `/proc/mounts`
+/// (cgroups, tmpfs, devpts, etc. can easily outnumber real mounts 10 to 1).
+const PSEUDO_FS_TYPES: &[&str] = &[
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "tmpfs",
+    "cgroup",
+    "cgroup2",
+    "pstore",
+    "securityfs",
+    "debugfs",
+    "tracefs",
+    "mqueue",
+    "hugetlbfs",
+    "fusectl",
+    "configfs",
+    "binfmt_misc",
+    "autofs",
+    "bpf",
+    "rpc_pipefs",
+    "nsfs",
+];
+
+/// Reads the platform mount table and attaches free-space stats, skipping
+/// pseudo filesystems and any mount point `statvfs` can't reach (e.g. stale
+/// autofs entries).
+pub fn list_mounts() -> Vec<Mount> {
+    ProcMounts
+        .entries()
+        .into_iter()
+        .filter(|(_, _, fs_type)| !PSEUDO_FS_TYPES.contains(&fs_type.as_str()))
+        .filter_map(|(device, mount_point, fs_type)| {
+            let (total, used, available) = statvfs_usage(&mount_point)?;
+            Some(Mount {
+                device,
+                mount_point,
+                fs_type,
+                total,
+                used,
+                available,
+            })
+        })
+        .collect()
+}
+
+fn statvfs_usage(path: &Path) -> Option<(u64, u64, u64)> {
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    if rc != 0 {
+        return None;
+    }
+    let stat = unsafe { stat.assume_init() };
+    let block_size = stat.f_frsize;
+    let total = stat.f_blocks * block_size;
+    let free = stat.f_bfree * block_size;
+    let available = stat.f_bavail * block_size;
+    Some((total, total.saturating_sub(free), available))
+}
+
+/// Formats a byte count like `"3.2G"`, matching the register of `ls -h`.
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{size:.0}{}", UNITS[unit])
+    } else {
+        format!("{size:.1}{}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_octal_decodes_space_and_backslash() {
+        assert_eq!(unescape_octal(r"foo\040bar"), "foo bar");
+        assert_eq!(unescape_octal(r"back\134slash"), "back\\slash");
+    }
+
+    #[test]
+    fn unescape_octal_passes_through_plain_text() {
+        assert_eq!(unescape_octal("/mnt/data"), "/mnt/data");
+    }
+
+    #[test]
+    fn unescape_octal_leaves_invalid_sequence_untouched() {
+        assert_eq!(unescape_octal(r"bad\xyz"), r"bad\xyz");
+    }
+
+    #[test]
+    fn human_size_picks_the_largest_unit_under_1024() {
+        assert_eq!(human_size(512), "512B");
+        assert_eq!(human_size(2048), "2.0K");
+        assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0G");
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    fs, io,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Copy,
+    Move,
+    Delete,
+}
+
+impl Op {
+    pub fn label(self) -> &'static str {
+        match self {
+            Op::Copy => "copying",
+            Op::Move => "moving",
+            Op::Delete => "deleting",
+        }
+    }
+}
+
+enum Progress {
+    Update {
+        done: usize,
+        total: usize,
+        bytes_done: u64,
+        bytes_total: u64,
+    },
+    Done,
+    Error(String),
+}
+
+/// A copy/move/delete running on a worker thread. Poll it from the event
+/// loop to pick up progress without blocking the UI.
+pub struct Task {
+    pub op: Op,
+    pub done: usize,
+    pub total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub error: Option<String>,
+    rx: Receiver<Progress>,
+}
+
+impl Task {
+    /// Spawns `op` over `paths` in the background. `dest_dir` is the
+    /// target directory for copy/move and is ignored for deletes. Byte
+    /// totals are computed on the worker thread and arrive with the first
+    /// `Progress::Update`, so they start at 0 rather than blocking the UI
+    /// thread on a recursive `fs::metadata` walk.
+    pub fn spawn(op: Op, paths: Vec<PathBuf>, dest_dir: Option<PathBuf>) -> Task {
+        let total = paths.len();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || run(op, paths, dest_dir, &tx));
+        Task {
+            op,
+            done: 0,
+            total,
+            bytes_done: 0,
+            bytes_total: 0,
+            error: None,
+            rx,
+        }
+    }
+
+    /// Drains any progress reported so far. Returns `true` once the task
+    /// has finished, whether it succeeded or hit an error.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok(progress) = self.rx.try_recv() {
+            match progress {
+                Progress::Update {
+                    done,
+                    total,
+                    bytes_done,
+                    bytes_total,
+                } => {
+                    self.done = done;
+                    self.total = total;
+                    self.bytes_done = bytes_done;
+                    self.bytes_total = bytes_total;
+                }
+                Progress::Done => finished = true,
+                Progress::Error(message) => {
+                    self.error = Some(message);
+                    finished = true;
+                }
+            }
+        }
+        finished
+    }
+}
+
+/// Bytes streamed per `Progress::Update` while copying a single file, so a
+/// large file's progress moves continuously instead of jumping straight
+/// from 0 to done.
+const COPY_CHUNK: usize = 256 * 1024;
+
+/// Threaded through a whole `run()` so every leaf copy can report byte
+/// progress without re-sending the surrounding file counts by hand.
+struct ProgressCtx<'a> {
+    tx: &'a Sender<Progress>,
+    files_done: usize,
+    files_total: usize,
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+fn run(op: Op, paths: Vec<PathBuf>, dest_dir: Option<PathBuf>, tx: &Sender<Progress>) {
+    let files_total = paths.len();
+    let sizes: Vec<u64> = paths.iter().map(|p| dir_size(p)).collect();
+    let bytes_total = sizes.iter().sum();
+    let mut ctx = ProgressCtx {
+        tx,
+        files_done: 0,
+        files_total,
+        bytes_done: 0,
+        bytes_total,
+    };
+
+    for (src, size) in paths.into_iter().zip(sizes) {
+        let result = match op {
+            Op::Copy => copy_into(&src, dest_dir.as_deref().expect("copy needs a destination"), &mut ctx),
+            Op::Move => move_into(&src, dest_dir.as_deref().expect("move needs a destination"), size, &mut ctx),
+            Op::Delete => trash::delete(&src)
+                .map(|_| ctx.add_bytes(size))
+                .map_err(|e| io::Error::other(e.to_string())),
+        };
+        if let Err(e) = result {
+            let _ = tx.send(Progress::Error(e.to_string()));
+            return;
+        }
+        ctx.files_done += 1;
+        ctx.report();
+    }
+    let _ = tx.send(Progress::Done);
+}
+
+impl ProgressCtx<'_> {
+    fn report(&self) {
+        let _ = self.tx.send(Progress::Update {
+            done: self.files_done,
+            total: self.files_total,
+            bytes_done: self.bytes_done,
+            bytes_total: self.bytes_total,
+        });
+    }
+
+    fn add_bytes(&mut self, delta: u64) {
+        self.bytes_done += delta;
+        self.report();
+    }
+}
+
+/// Total size in bytes of `path`, recursing into directories. Symlinks are
+/// not followed and count as their own length. Entries that vanish, and
+/// directories that can't be read (permissions), contribute 0 rather than
+/// failing the whole walk.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries.flatten().map(|entry| dir_size(&entry.path())).sum()
+}
+
+fn copy_into(src: &Path, dest_dir: &Path, ctx: &mut ProgressCtx) -> io::Result<()> {
+    reject_paste_into_self(src, dest_dir)?;
+    copy_recursive(src, &unique_dest(dest_dir, src), ctx)
+}
+
+fn move_into(src: &Path, dest_dir: &Path, size: u64, ctx: &mut ProgressCtx) -> io::Result<()> {
+    reject_paste_into_self(src, dest_dir)?;
+    let dest = unique_dest(dest_dir, src);
+    if fs::rename(src, &dest).is_ok() {
+        ctx.add_bytes(size);
+        return Ok(());
+    }
+    copy_recursive(src, &dest, ctx)?;
+    if src.is_dir() {
+        fs::remove_dir_all(src)
+    } else {
+        fs::remove_file(src)
+    }
+}
+
+/// Pasting a directory into itself or a descendant of itself would make
+/// `copy_recursive`'s `create_dir_all(dest)` land inside `src`, so the
+/// following `read_dir(src)` sees that freshly created entry and recurses
+/// into it forever (until a path-length error eventually aborts it), after
+/// burning disk and CPU and leaving a partial tree behind. Refuse up front
+/// instead.
+fn reject_paste_into_self(src: &Path, dest_dir: &Path) -> io::Result<()> {
+    if dest_dir == src || dest_dir.starts_with(src) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("can't paste {} into itself", src.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn copy_recursive(src: &Path, dest: &Path, ctx: &mut ProgressCtx) -> io::Result<()> {
+    if src.is_dir() {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &dest.join(entry.file_name()), ctx)?;
+        }
+        Ok(())
+    } else {
+        copy_file_with_progress(src, dest, ctx)
+    }
+}
+
+/// Copies `src` to `dest` in `COPY_CHUNK`-sized reads, reporting each
+/// chunk, instead of `fs::copy`'s single all-or-nothing syscall.
+fn copy_file_with_progress(src: &Path, dest: &Path, ctx: &mut ProgressCtx) -> io::Result<()> {
+    let mut reader = fs::File::open(src)?;
+    let mut writer = fs::File::create(dest)?;
+    let mut buf = [0u8; COPY_CHUNK];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        writer.write_all(&buf[..n])?;
+        ctx.add_bytes(n as u64);
+    }
+}
+
+/// Appends a numeric suffix (`name (1).ext`) until `dest_dir` has no entry
+/// by that name, so pasting never clobbers an existing file.
+fn unique_dest(dest_dir: &Path, src: &Path) -> PathBuf {
+    let name = src.file_name().expect("source has a file name");
+    let dest = dest_dir.join(name);
+    if !dest.exists() {
+        return dest;
+    }
+
+    let stem = Path::new(name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = Path::new(name)
+        .extension()
+        .map(|s| s.to_string_lossy().into_owned());
+
+    let mut n = 1;
+    loop {
+        let candidate = match &ext {
+            Some(ext) => format!("{stem} ({n}).{ext}"),
+            None => format!("{stem} ({n})"),
+        };
+        let dest = dest_dir.join(candidate);
+        if !dest.exists() {
+            return dest;
+        }
+        n += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("seldir2-ops-test-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unique_dest_keeps_the_name_when_free() {
+        let dir = temp_dir("free");
+        let dest = unique_dest(&dir, Path::new("/src/file.txt"));
+        assert_eq!(dest, dir.join("file.txt"));
+    }
+
+    #[test]
+    fn unique_dest_appends_a_numeric_suffix_on_collision() {
+        let dir = temp_dir("collision");
+        fs::write(dir.join("file.txt"), b"existing").unwrap();
+        let dest = unique_dest(&dir, Path::new("/src/file.txt"));
+        assert_eq!(dest, dir.join("file (1).txt"));
+    }
+
+    #[test]
+    fn unique_dest_skips_suffixes_already_taken() {
+        let dir = temp_dir("skip");
+        fs::write(dir.join("file.txt"), b"existing").unwrap();
+        fs::write(dir.join("file (1).txt"), b"existing").unwrap();
+        let dest = unique_dest(&dir, Path::new("/src/file.txt"));
+        assert_eq!(dest, dir.join("file (2).txt"));
+    }
+
+    #[test]
+    fn unique_dest_suffixes_extensionless_names() {
+        let dir = temp_dir("noext");
+        fs::write(dir.join("README"), b"existing").unwrap();
+        let dest = unique_dest(&dir, Path::new("/src/README"));
+        assert_eq!(dest, dir.join("README (1)"));
+    }
+
+    #[test]
+    fn dir_size_sums_a_single_file() {
+        let dir = temp_dir("size-file");
+        fs::write(dir.join("a.txt"), b"hello").unwrap();
+        assert_eq!(dir_size(&dir.join("a.txt")), 5);
+    }
+
+    #[test]
+    fn dir_size_recurses_into_subdirectories() {
+        let dir = temp_dir("size-tree");
+        fs::write(dir.join("a.txt"), b"hello").unwrap();
+        fs::create_dir(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub/b.txt"), b"hi").unwrap();
+        assert_eq!(dir_size(&dir), 7);
+    }
+
+    fn test_ctx(tx: &Sender<Progress>) -> ProgressCtx<'_> {
+        ProgressCtx {
+            tx,
+            files_done: 0,
+            files_total: 1,
+            bytes_done: 0,
+            bytes_total: 0,
+        }
+    }
+
+    #[test]
+    fn copy_into_rejects_pasting_a_directory_into_itself() {
+        let dir = temp_dir("copy-into-self");
+        let (tx, _rx) = mpsc::channel();
+        let mut ctx = test_ctx(&tx);
+        let err = copy_into(&dir, &dir, &mut ctx).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn copy_into_rejects_pasting_a_directory_into_its_own_subdirectory() {
+        let dir = temp_dir("copy-into-descendant");
+        let sub = dir.join("sub");
+        fs::create_dir(&sub).unwrap();
+        let (tx, _rx) = mpsc::channel();
+        let mut ctx = test_ctx(&tx);
+        let err = copy_into(&dir, &sub, &mut ctx).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(fs::read_dir(&dir).unwrap().count() == 1, "no garbage entries should be created under src");
+    }
+
+    #[test]
+    fn move_into_rejects_pasting_a_directory_into_itself() {
+        let dir = temp_dir("move-into-self");
+        let (tx, _rx) = mpsc::channel();
+        let mut ctx = test_ctx(&tx);
+        let err = move_into(&dir, &dir, 0, &mut ctx).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
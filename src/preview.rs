@@ -0,0 +1,289 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    highlighting::{
+        Highlighter as SynHighlighter, HighlightIterator, HighlightState, Style as SynStyle, Theme, ThemeSet,
+    },
+    parsing::{ParseState, ScopeStack, SyntaxSet},
+};
+
+/// Largest slice of a file we'll read for a preview, so opening a huge log
+/// or binary doesn't stall the 200 ms tick.
+const PREVIEW_CAP: usize = 64 * 1024;
+
+/// Bytes of a binary file shown in the fallback hex/byte summary.
+const HEX_DUMP_LEN: usize = 256;
+
+pub enum PreviewContent {
+    Text { lines: Vec<String>, ext: String },
+    Binary { len: u64, dump: String },
+    Empty,
+}
+
+pub struct Preview {
+    pub content: PreviewContent,
+    /// First line shown in the viewport; moved by `scroll`, reset to 0
+    /// every time a new file is loaded.
+    pub top: usize,
+    /// Highlighter checkpoints for this file's lines, reset on every load.
+    pub highlight_cache: HighlightCache,
+}
+
+impl Preview {
+    pub fn load(path: &Path) -> Preview {
+        let Ok(file) = File::open(path) else {
+            return Preview {
+                content: PreviewContent::Empty,
+                top: 0,
+                highlight_cache: HighlightCache::new(),
+            };
+        };
+        let mut capped = Vec::new();
+        if file.take(PREVIEW_CAP as u64).read_to_end(&mut capped).is_err() {
+            return Preview {
+                content: PreviewContent::Empty,
+                top: 0,
+                highlight_cache: HighlightCache::new(),
+            };
+        }
+
+        // A cap mid-file can split a multi-byte UTF-8 sequence at the very
+        // end of `capped`; that shows up as an "unexpected end of input"
+        // error rather than a genuinely invalid byte, so only drop the
+        // dangling partial character instead of classifying the whole file
+        // as binary.
+        let text = match std::str::from_utf8(&capped) {
+            Ok(text) => Some(text),
+            Err(e) if e.error_len().is_none() => std::str::from_utf8(&capped[..e.valid_up_to()]).ok(),
+            Err(_) => None,
+        };
+
+        match text {
+            Some(text) => {
+                let ext = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("")
+                    .to_owned();
+                let lines = text.lines().map(str::to_owned).collect();
+                Preview {
+                    content: PreviewContent::Text { lines, ext },
+                    top: 0,
+                    highlight_cache: HighlightCache::new(),
+                }
+            }
+            None => {
+                let len = fs::metadata(path).map(|m| m.len()).unwrap_or(capped.len() as u64);
+                let dump = hex_dump(&capped[..capped.len().min(HEX_DUMP_LEN)]);
+                Preview {
+                    content: PreviewContent::Binary { len, dump },
+                    top: 0,
+                    highlight_cache: HighlightCache::new(),
+                }
+            }
+        }
+    }
+
+    /// Moves the viewport by `delta` lines (negative scrolls up), clamped
+    /// so at least one line stays visible. A no-op for binary/empty
+    /// previews, which have nothing to scroll.
+    pub fn scroll(&mut self, delta: isize) {
+        let PreviewContent::Text { lines, .. } = &self.content else {
+            return;
+        };
+        let max = lines.len().saturating_sub(1);
+        self.top = (self.top as isize + delta).clamp(0, max as isize) as usize;
+    }
+}
+
+fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02x} "))
+        .collect::<String>()
+}
+
+/// Holds the loaded syntax/theme sets so they're parsed once instead of on
+/// every preview, and highlights only the rows currently on screen.
+pub struct Highlighter {
+    syntax_set: SyntaxSet,
+    theme: Theme,
+}
+
+impl Highlighter {
+    pub fn new() -> Highlighter {
+        let theme_set = ThemeSet::load_defaults();
+        Highlighter {
+            syntax_set: SyntaxSet::load_defaults_newlines(),
+            theme: theme_set.themes["base16-ocean.dark"].clone(),
+        }
+    }
+
+    /// Highlights `lines[top..top + height]`, leaving the rest untouched.
+    ///
+    /// syntect's line highlighter carries state across calls (open block
+    /// comments, multi-line strings, ...), so seeding it directly at `top`
+    /// would lose whatever scope was open when scrolling past the first
+    /// screen. Replaying from line 0 on every redraw fixed that but made
+    /// every tick re-tokenize the whole file above the viewport, so `cache`
+    /// keeps periodic `HighlightCache` checkpoints for this file and we
+    /// resume from one strictly before `top` instead.
+    pub fn highlight_viewport(
+        &self,
+        lines: &[String],
+        ext: &str,
+        top: usize,
+        height: usize,
+        cache: &mut HighlightCache,
+    ) -> Vec<Line<'static>> {
+        let syntax = self
+            .syntax_set
+            .find_syntax_by_extension(ext)
+            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+        let highlighter = SynHighlighter::new(&self.theme);
+
+        let (start, mut parse_state, mut highlight_state) = match cache.checkpoint_before(top) {
+            Some((line, parse_state, highlight_state)) => (line + 1, parse_state, highlight_state),
+            None => (0, ParseState::new(syntax), HighlightState::new(&highlighter, ScopeStack::new())),
+        };
+
+        let end = (top + height).min(lines.len());
+        let mut out = Vec::with_capacity(end.saturating_sub(top));
+        for (idx, line) in lines[start..end].iter().enumerate().map(|(i, l)| (start + i, l)) {
+            let ops = parse_state.parse_line(line, &self.syntax_set).unwrap_or_default();
+            let ranges: Vec<(SynStyle, &str)> =
+                HighlightIterator::new(&mut highlight_state, &ops, line, &highlighter).collect();
+            if idx % CHECKPOINT_STRIDE == 0 {
+                cache.store(idx, parse_state.clone(), highlight_state.clone());
+            }
+            if idx >= top {
+                out.push(Line::from(
+                    ranges
+                        .into_iter()
+                        .map(|(style, text)| Span::styled(text.to_owned(), syn_style(style)))
+                        .collect::<Vec<_>>(),
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Lines between checkpoints that may need replaying before reaching `top`.
+const CHECKPOINT_STRIDE: usize = 64;
+
+/// Parser/highlight-state snapshots taken every `CHECKPOINT_STRIDE` lines
+/// while highlighting a file's viewport, so scrolling back to an
+/// already-visited line resumes nearby instead of replaying from line 0.
+/// Scoped to one loaded file; `Preview::load` starts a fresh cache.
+pub struct HighlightCache {
+    checkpoints: Vec<(usize, ParseState, HighlightState)>,
+}
+
+impl HighlightCache {
+    pub fn new() -> HighlightCache {
+        HighlightCache { checkpoints: Vec::new() }
+    }
+
+    /// The latest checkpoint strictly before `line`, if any, cloned so the
+    /// caller can advance it without disturbing the cache.
+    fn checkpoint_before(&self, line: usize) -> Option<(usize, ParseState, HighlightState)> {
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|(idx, _, _)| *idx < line)
+            .map(|(idx, parse_state, highlight_state)| (*idx, parse_state.clone(), highlight_state.clone()))
+    }
+
+    fn store(&mut self, line: usize, parse_state: ParseState, highlight_state: HighlightState) {
+        if self.checkpoints.last().map(|(idx, ..)| *idx) != Some(line) {
+            self.checkpoints.push((line, parse_state, highlight_state));
+        }
+    }
+}
+
+fn syn_style(style: SynStyle) -> Style {
+    Style::default().fg(Color::Rgb(
+        style.foreground.r,
+        style.foreground.g,
+        style.foreground.b,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("seldir2-preview-test-{name}-{}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn hex_dump_renders_space_separated_lowercase_bytes() {
+        assert_eq!(hex_dump(&[0x00, 0x1f, 0xff]), "00 1f ff ");
+    }
+
+    #[test]
+    fn load_reads_a_text_file_into_lines() {
+        let path = temp_file("text", b"one\ntwo\nthree");
+        let preview = Preview::load(&path);
+        let PreviewContent::Text { lines, ext } = preview.content else {
+            panic!("expected text content");
+        };
+        assert_eq!(lines, vec!["one", "two", "three"]);
+        assert_eq!(ext, "");
+    }
+
+    #[test]
+    fn load_classifies_non_utf8_bytes_as_binary() {
+        let path = temp_file("binary", &[0x00, 0x9f, 0x92, 0x28]);
+        let preview = Preview::load(&path);
+        assert!(matches!(preview.content, PreviewContent::Binary { .. }));
+    }
+
+    #[test]
+    fn highlight_viewport_is_stable_across_a_warm_cache() {
+        let highlighter = Highlighter::new();
+        let lines: Vec<String> = (0..200).map(|i| format!("let x{i} = {i};")).collect();
+        let mut cache = HighlightCache::new();
+
+        let cold = highlighter.highlight_viewport(&lines, "rs", 150, 10, &mut cache);
+        assert!(!cache.checkpoints.is_empty(), "checkpoints should be recorded on the way to line 150");
+        let warm = highlighter.highlight_viewport(&lines, "rs", 150, 10, &mut cache);
+
+        let as_text = |rendered: &[Line]| {
+            rendered
+                .iter()
+                .map(|line| line.spans.iter().map(|span| span.content.clone()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(as_text(&cold), as_text(&warm));
+    }
+
+    #[test]
+    fn load_drops_a_multi_byte_char_split_by_the_read_cap() {
+        // "é" is 2 bytes (0xC3 0xA9); capping the read right after the
+        // leading byte must drop the dangling half instead of treating
+        // the whole file as binary.
+        let mut contents = vec![b'a'; PREVIEW_CAP - 1];
+        contents.push(0xC3);
+        contents.push(0xA9);
+        let path = temp_file("cap-boundary", &contents);
+        let preview = Preview::load(&path);
+        let PreviewContent::Text { lines, .. } = preview.content else {
+            panic!("expected text content, cap split should not make this binary");
+        };
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].len(), PREVIEW_CAP - 1);
+    }
+}
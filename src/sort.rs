@@ -0,0 +1,182 @@
+use std::{cmp::Ordering, fs::DirEntry, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    Name,
+    Natural,
+    Size,
+    Modified,
+    Extension,
+}
+
+impl Sort {
+    /// Cycles through the variants in the order keybindings advance them.
+    pub fn next(self) -> Sort {
+        match self {
+            Sort::Name => Sort::Natural,
+            Sort::Natural => Sort::Size,
+            Sort::Size => Sort::Modified,
+            Sort::Modified => Sort::Extension,
+            Sort::Extension => Sort::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Sort::Name => "name",
+            Sort::Natural => "natural",
+            Sort::Size => "size",
+            Sort::Modified => "modified",
+            Sort::Extension => "extension",
+        }
+    }
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Sort, String> {
+        match s {
+            "name" => Ok(Sort::Name),
+            "natural" => Ok(Sort::Natural),
+            "size" => Ok(Sort::Size),
+            "modified" => Ok(Sort::Modified),
+            "extension" => Ok(Sort::Extension),
+            other => Err(format!(
+                "unknown sort \"{other}\" (expected name, natural, size, modified or extension)"
+            )),
+        }
+    }
+}
+
+/// The sort behavior shared by every pane; cheap to copy so it can be
+/// passed around alongside `show_hidden` instead of living only on `App`.
+#[derive(Debug, Clone, Copy)]
+pub struct SortConfig {
+    pub sort: Sort,
+    pub reverse: bool,
+    pub dirs_first: bool,
+}
+
+/// Sorts `entries` in place, reading each entry's metadata exactly once
+/// rather than on every comparison.
+pub fn sort_entries(entries: &mut Vec<DirEntry>, config: SortConfig) {
+    let mut annotated: Vec<(DirEntry, Option<std::fs::Metadata>)> = entries
+        .drain(..)
+        .map(|entry| {
+            let metadata = entry.metadata().ok();
+            (entry, metadata)
+        })
+        .collect();
+
+    annotated.sort_by(|(a, a_meta), (b, b_meta)| {
+        if config.dirs_first {
+            let a_dir = a_meta.as_ref().is_some_and(std::fs::Metadata::is_dir);
+            let b_dir = b_meta.as_ref().is_some_and(std::fs::Metadata::is_dir);
+            match (a_dir, b_dir) {
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                _ => {}
+            }
+        }
+
+        let ordering = match config.sort {
+            Sort::Name => name(a).cmp(&name(b)),
+            Sort::Natural => natural_cmp(&name(a), &name(b)),
+            Sort::Size => len(a_meta).cmp(&len(b_meta)),
+            Sort::Modified => modified(a_meta).cmp(&modified(b_meta)),
+            Sort::Extension => extension(a).cmp(&extension(b)).then_with(|| name(a).cmp(&name(b))),
+        };
+
+        if config.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    *entries = annotated.into_iter().map(|(entry, _)| entry).collect();
+}
+
+fn name(entry: &DirEntry) -> String {
+    entry.file_name().to_string_lossy().into_owned()
+}
+
+fn extension(entry: &DirEntry) -> String {
+    entry
+        .path()
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn len(metadata: &Option<std::fs::Metadata>) -> u64 {
+    metadata.as_ref().map(std::fs::Metadata::len).unwrap_or(0)
+}
+
+fn modified(metadata: &Option<std::fs::Metadata>) -> Option<std::time::SystemTime> {
+    metadata.as_ref().and_then(|m| m.modified().ok())
+}
+
+/// Compares names by splitting them into alternating digit/non-digit runs
+/// and comparing digit runs numerically, so `file2` sorts before `file10`.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_runs = split_runs(a).into_iter();
+    let mut b_runs = split_runs(b).into_iter();
+    loop {
+        match (a_runs.next(), b_runs.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ar), Some(br)) => {
+                let ordering = match (ar.parse::<u64>(), br.parse::<u64>()) {
+                    (Ok(an), Ok(bn)) => an.cmp(&bn),
+                    _ => ar.cmp(&br),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+fn split_runs(s: &str) -> Vec<String> {
+    let mut runs = Vec::new();
+    let mut current = String::new();
+    let mut current_is_digit = None;
+    for c in s.chars() {
+        let is_digit = c.is_ascii_digit();
+        if current_is_digit != Some(is_digit) && !current.is_empty() {
+            runs.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+        current_is_digit = Some(is_digit);
+    }
+    if !current.is_empty() {
+        runs.push(current);
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_numerically() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+    }
+
+    #[test]
+    fn natural_cmp_falls_back_to_lexical_on_non_digit_runs() {
+        assert_eq!(natural_cmp("abc", "abd"), Ordering::Less);
+        assert_eq!(natural_cmp("file", "file"), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_treats_a_shorter_prefix_as_smaller() {
+        assert_eq!(natural_cmp("file", "file2"), Ordering::Less);
+    }
+}
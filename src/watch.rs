@@ -0,0 +1,93 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher as _};
+
+/// Bursty FS events (e.g. a build writing dozens of files) are coalesced
+/// within this window before being forwarded to the event loop.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Watches a small, explicit set of directories (non-recursively) and
+/// delivers debounced "this directory changed" notifications.
+pub struct DirWatcher {
+    watcher: RecommendedWatcher,
+    watched: HashSet<PathBuf>,
+    rx: Receiver<PathBuf>,
+}
+
+impl DirWatcher {
+    pub fn new() -> notify::Result<DirWatcher> {
+        let (raw_tx, raw_rx) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                for path in event.paths {
+                    let _ = raw_tx.send(path);
+                }
+            }
+        })?;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || debounce(raw_rx, tx));
+
+        Ok(DirWatcher {
+            watcher,
+            watched: HashSet::new(),
+            rx,
+        })
+    }
+
+    /// Replaces the watched set with exactly `dirs`, unwatching anything
+    /// no longer needed (e.g. the old left/right panes after navigation).
+    pub fn set_watched(&mut self, dirs: &[PathBuf]) {
+        let wanted: HashSet<PathBuf> = dirs.iter().cloned().collect();
+        for stale in self.watched.difference(&wanted) {
+            let _ = self.watcher.unwatch(stale);
+        }
+        for fresh in wanted.difference(&self.watched) {
+            let _ = self.watcher.watch(fresh, RecursiveMode::NonRecursive);
+        }
+        self.watched = wanted;
+    }
+
+    /// Non-blocking poll for a directory that changed since the last call.
+    pub fn try_recv(&self) -> Option<PathBuf> {
+        self.rx.try_recv().ok()
+    }
+}
+
+fn debounce(raw_rx: Receiver<PathBuf>, tx: Sender<PathBuf>) {
+    loop {
+        let Ok(first) = raw_rx.recv() else {
+            return;
+        };
+        let mut pending = HashSet::new();
+        pending.insert(parent_dir(&first));
+        loop {
+            match raw_rx.recv_timeout(DEBOUNCE) {
+                Ok(path) => {
+                    pending.insert(parent_dir(&path));
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        for path in pending {
+            if tx.send(path).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// notify reports the changed file/entry itself; the pane we render is
+/// keyed on the containing directory.
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| path.to_path_buf())
+}